@@ -6,8 +6,9 @@ extern crate bit_set;
 extern crate bit_vec;
 extern crate vec_map;
 
+use std::cmp::Ordering;
 use std::cmp::Ordering::*;
-use std::iter::Peekable;
+use std::iter::{Peekable, FusedIterator};
 use std::collections::{
     btree_map, btree_set,
 };
@@ -52,6 +53,76 @@ pub trait OrderedMapIterator: Iterator<Item=(<Self as OrderedMapIterator>::Key,
             right: other.peekable()
         }
     }
+
+    /// Computes the changes needed to turn `self` into `other`.
+    ///
+    /// Yields a [`DiffItem`] for every key that is only in `self`
+    /// (`Removed`), only in `other` (`Added`), or in both with a
+    /// different value (`Update`). Keys present in both with equal
+    /// values are skipped.
+    fn diff<I>(self, other: I) -> Diff<Self, I>
+    where I: OrderedMapIterator<Key=Self::Key, Val=Self::Val> {
+        Diff {
+            a: self.peekable(),
+            b: other.peekable()
+        }
+    }
+
+    /// Joins two ordered maps together, comparing keys with `cmp` instead
+    /// of their `Ord` implementation.
+    ///
+    /// Both `self` and `map` must already be sorted consistently with
+    /// `cmp`.
+    fn inner_join_map_by<I, C>(self, map: I, cmp: C) -> InnerJoinMapBy<Self, I, C>
+    where I: OrderedMapIterator<Key=Self::Key>,
+          C: FnMut(&Self::Key, &Self::Key) -> ::std::cmp::Ordering {
+        InnerJoinMapBy {
+            a: self,
+            b: map,
+            cmp
+        }
+    }
+
+    /// Joins two ordered maps together, comparing keys by the value
+    /// returned from `key`.
+    ///
+    /// Both `self` and `map` must already be sorted consistently with
+    /// the order of `key`'s return value.
+    fn inner_join_map_by_key<I, F, K2>(self, map: I, key: F) -> InnerJoinMapByKey<Self, I, F, K2>
+    where I: OrderedMapIterator<Key=Self::Key>,
+          F: FnMut(&Self::Key) -> K2,
+          K2: Ord {
+        InnerJoinMapByKey {
+            a: Keyed::new(self),
+            b: Keyed::new(map),
+            key
+        }
+    }
+
+    /// Like [`OrderedMapIterator::outer_join`], but comparing keys with
+    /// `cmp` instead of their `Ord` implementation.
+    fn outer_join_by<I, C>(self, other: I, cmp: C) -> OuterJoinBy<Self, I, C>
+    where I: OrderedMapIterator<Key=Self::Key>,
+          C: FnMut(&Self::Key, &Self::Key) -> ::std::cmp::Ordering {
+        OuterJoinBy {
+            left: self.peekable(),
+            right: other.peekable(),
+            cmp
+        }
+    }
+
+    /// Like [`OrderedMapIterator::outer_join`], but comparing keys by the
+    /// value returned from `key`.
+    fn outer_join_by_key<I, F, K2>(self, other: I, key: F) -> OuterJoinByKey<Self, I, F, K2>
+    where I: OrderedMapIterator<Key=Self::Key>,
+          F: FnMut(&Self::Key) -> K2,
+          K2: Ord {
+        OuterJoinByKey {
+            left: Keyed::new(self),
+            right: Keyed::new(other),
+            key
+        }
+    }
 }
 
 /// Allows an iterator to do an inner join with another
@@ -76,6 +147,134 @@ pub trait OrderedSetIterator: Iterator + Sized {
             b: map
         }
     }
+
+    /// Merges two ordered sets together, returning every item present in
+    /// either set. An item present in both sets is only returned once.
+    fn union<I>(self, other: I) -> Union<Self, I>
+    where I: OrderedSetIterator<Item=Self::Item> {
+        Union {
+            a: self.peekable(),
+            b: other.peekable()
+        }
+    }
+
+    /// Returns every item in `self` that is not also present in `other`.
+    fn difference<I>(self, other: I) -> Difference<Self, I>
+    where I: OrderedSetIterator<Item=Self::Item> {
+        Difference {
+            a: self.peekable(),
+            b: other.peekable()
+        }
+    }
+
+    /// Returns every item present in exactly one of the two sets.
+    fn symmetric_difference<I>(self, other: I) -> SymmetricDifference<Self, I>
+    where I: OrderedSetIterator<Item=Self::Item> {
+        SymmetricDifference {
+            a: self.peekable(),
+            b: other.peekable()
+        }
+    }
+
+    /// Like [`OrderedSetIterator::inner_join_set`], but comparing items
+    /// with `cmp` instead of their `Ord` implementation.
+    fn inner_join_set_by<I, C>(self, other: I, cmp: C) -> InnerJoinSetBy<Self, I, C>
+    where I: OrderedSetIterator<Item=Self::Item>,
+          C: FnMut(&Self::Item, &Self::Item) -> ::std::cmp::Ordering {
+        InnerJoinSetBy {
+            a: self,
+            b: other,
+            cmp
+        }
+    }
+
+    /// Like [`OrderedSetIterator::inner_join_set`], but comparing items by
+    /// the value returned from `key`.
+    fn inner_join_set_by_key<I, F, K2>(self, other: I, key: F) -> InnerJoinSetByKey<Self, I, F, K2>
+    where I: OrderedSetIterator<Item=Self::Item>,
+          F: FnMut(&Self::Item) -> K2,
+          K2: Ord {
+        InnerJoinSetByKey {
+            a: Keyed::new(self),
+            b: Keyed::new(other),
+            key
+        }
+    }
+
+    /// Like [`OrderedSetIterator::union`], but comparing items with `cmp`
+    /// instead of their `Ord` implementation.
+    fn union_by<I, C>(self, other: I, cmp: C) -> UnionBy<Self, I, C>
+    where I: OrderedSetIterator<Item=Self::Item>,
+          C: FnMut(&Self::Item, &Self::Item) -> ::std::cmp::Ordering {
+        UnionBy {
+            a: self.peekable(),
+            b: other.peekable(),
+            cmp
+        }
+    }
+
+    /// Like [`OrderedSetIterator::union`], but comparing items by the
+    /// value returned from `key`.
+    fn union_by_key<I, F, K2>(self, other: I, key: F) -> UnionByKey<Self, I, F, K2>
+    where I: OrderedSetIterator<Item=Self::Item>,
+          F: FnMut(&Self::Item) -> K2,
+          K2: Ord {
+        UnionByKey {
+            a: Keyed::new(self),
+            b: Keyed::new(other),
+            key
+        }
+    }
+
+    /// Like [`OrderedSetIterator::difference`], but comparing items with
+    /// `cmp` instead of their `Ord` implementation.
+    fn difference_by<I, C>(self, other: I, cmp: C) -> DifferenceBy<Self, I, C>
+    where I: OrderedSetIterator<Item=Self::Item>,
+          C: FnMut(&Self::Item, &Self::Item) -> ::std::cmp::Ordering {
+        DifferenceBy {
+            a: self.peekable(),
+            b: other.peekable(),
+            cmp
+        }
+    }
+
+    /// Like [`OrderedSetIterator::difference`], but comparing items by the
+    /// value returned from `key`.
+    fn difference_by_key<I, F, K2>(self, other: I, key: F) -> DifferenceByKey<Self, I, F, K2>
+    where I: OrderedSetIterator<Item=Self::Item>,
+          F: FnMut(&Self::Item) -> K2,
+          K2: Ord {
+        DifferenceByKey {
+            a: Keyed::new(self),
+            b: Keyed::new(other),
+            key
+        }
+    }
+
+    /// Like [`OrderedSetIterator::symmetric_difference`], but comparing
+    /// items with `cmp` instead of their `Ord` implementation.
+    fn symmetric_difference_by<I, C>(self, other: I, cmp: C) -> SymmetricDifferenceBy<Self, I, C>
+    where I: OrderedSetIterator<Item=Self::Item>,
+          C: FnMut(&Self::Item, &Self::Item) -> ::std::cmp::Ordering {
+        SymmetricDifferenceBy {
+            a: self.peekable(),
+            b: other.peekable(),
+            cmp
+        }
+    }
+
+    /// Like [`OrderedSetIterator::symmetric_difference`], but comparing
+    /// items by the value returned from `key`.
+    fn symmetric_difference_by_key<I, F, K2>(self, other: I, key: F) -> SymmetricDifferenceByKey<Self, I, F, K2>
+    where I: OrderedSetIterator<Item=Self::Item>,
+          F: FnMut(&Self::Item) -> K2,
+          K2: Ord {
+        SymmetricDifferenceByKey {
+            a: Keyed::new(self),
+            b: Keyed::new(other),
+            key
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -96,6 +295,273 @@ where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone {
     }
 }
 
+/// A single change between two ordered maps, as produced by
+/// [`OrderedMapIterator::diff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffItem<K, V> {
+    /// The key was only present in the right-hand map.
+    Added(K, V),
+    /// The key was only present in the left-hand map.
+    Removed(K, V),
+    /// The key was present in both maps, with different values.
+    Update {
+        key: K,
+        old: V,
+        new: V
+    }
+}
+
+pub struct Diff<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A, B> Clone for Diff<A, B>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone {
+    fn clone(&self) -> Diff<A, B> {
+        Diff { a: self.a.clone(), b: self.b.clone() }
+    }
+}
+
+pub struct Union<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A, B> Clone for Union<A, B>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone {
+    fn clone(&self) -> Union<A, B> {
+        Union { a: self.a.clone(), b: self.b.clone() }
+    }
+}
+
+pub struct Difference<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A, B> Clone for Difference<A, B>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone {
+    fn clone(&self) -> Difference<A, B> {
+        Difference { a: self.a.clone(), b: self.b.clone() }
+    }
+}
+
+pub struct SymmetricDifference<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A, B> Clone for SymmetricDifference<A, B>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone {
+    fn clone(&self) -> SymmetricDifference<A, B> {
+        SymmetricDifference { a: self.a.clone(), b: self.b.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct InnerJoinMapBy<A, B, C> {a: A, b: B, cmp: C}
+#[derive(Clone)]
+pub struct InnerJoinSetBy<A, B, C> {a: A, b: B, cmp: C}
+
+pub struct OuterJoinBy<A: Iterator, B: Iterator, C> {
+    left: Peekable<A>,
+    right: Peekable<B>,
+    cmp: C,
+}
+
+impl<A, B, C> Clone for OuterJoinBy<A, B, C>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, C: Clone {
+    fn clone(&self) -> OuterJoinBy<A, B, C> {
+        OuterJoinBy { left: self.left.clone(), right: self.right.clone(), cmp: self.cmp.clone() }
+    }
+}
+
+pub struct UnionBy<A: Iterator, B: Iterator, C> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    cmp: C,
+}
+
+impl<A, B, C> Clone for UnionBy<A, B, C>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, C: Clone {
+    fn clone(&self) -> UnionBy<A, B, C> {
+        UnionBy { a: self.a.clone(), b: self.b.clone(), cmp: self.cmp.clone() }
+    }
+}
+
+pub struct DifferenceBy<A: Iterator, B: Iterator, C> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    cmp: C,
+}
+
+impl<A, B, C> Clone for DifferenceBy<A, B, C>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, C: Clone {
+    fn clone(&self) -> DifferenceBy<A, B, C> {
+        DifferenceBy { a: self.a.clone(), b: self.b.clone(), cmp: self.cmp.clone() }
+    }
+}
+
+pub struct SymmetricDifferenceBy<A: Iterator, B: Iterator, C> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+    cmp: C,
+}
+
+impl<A, B, C> Clone for SymmetricDifferenceBy<A, B, C>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, C: Clone {
+    fn clone(&self) -> SymmetricDifferenceBy<A, B, C> {
+        SymmetricDifferenceBy { a: self.a.clone(), b: self.b.clone(), cmp: self.cmp.clone() }
+    }
+}
+
+/// Wraps an iterator so that the key of its next item can be projected
+/// and peeked at without re-running the key function on every
+/// comparison, and without losing fusedness when `I` is not itself
+/// fused.
+///
+/// Once `I` has yielded `None`, that result is cached for good, so a
+/// `Keyed` wrapper is always a fused iterator regardless of `I`.
+struct Keyed<I: Iterator, K2> {
+    iter: I,
+    peeked: Option<Option<(K2, I::Item)>>,
+}
+
+impl<I: Iterator, K2> Keyed<I, K2> {
+    fn new(iter: I) -> Keyed<I, K2> {
+        Keyed { iter, peeked: None }
+    }
+
+    fn peek_key<F>(&mut self, key: &mut F) -> Option<&K2>
+    where F: FnMut(&I::Item) -> K2 {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.iter.next().map(|item| {
+                let k = key(&item);
+                (k, item)
+            }));
+        }
+
+        match self.peeked {
+            Some(Some((ref k, _))) => Some(k),
+            _ => None
+        }
+    }
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.peeked.take() {
+            Some(Some((_, item))) => Some(item),
+            Some(None) => {
+                self.peeked = Some(None);
+                None
+            },
+            None => {
+                let item = self.iter.next();
+                if item.is_none() {
+                    self.peeked = Some(None);
+                }
+                item
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.peeked {
+            Some(None) => (0, Some(0)),
+            Some(Some(_)) => {
+                let (lower, upper) = self.iter.size_hint();
+                (lower.saturating_add(1), upper.map(|upper| upper.saturating_add(1)))
+            },
+            None => self.iter.size_hint()
+        }
+    }
+}
+
+impl<I, K2> Clone for Keyed<I, K2>
+where I: Clone + Iterator, I::Item: Clone, K2: Clone {
+    fn clone(&self) -> Keyed<I, K2> {
+        Keyed { iter: self.iter.clone(), peeked: self.peeked.clone() }
+    }
+}
+
+pub struct InnerJoinMapByKey<A: Iterator, B: Iterator, F, K2> {
+    a: Keyed<A, K2>,
+    b: Keyed<B, K2>,
+    key: F,
+}
+
+impl<A, B, F, K2> Clone for InnerJoinMapByKey<A, B, F, K2>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, F: Clone, K2: Clone {
+    fn clone(&self) -> InnerJoinMapByKey<A, B, F, K2> {
+        InnerJoinMapByKey { a: self.a.clone(), b: self.b.clone(), key: self.key.clone() }
+    }
+}
+
+pub struct InnerJoinSetByKey<A: Iterator, B: Iterator, F, K2> {
+    a: Keyed<A, K2>,
+    b: Keyed<B, K2>,
+    key: F,
+}
+
+impl<A, B, F, K2> Clone for InnerJoinSetByKey<A, B, F, K2>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, F: Clone, K2: Clone {
+    fn clone(&self) -> InnerJoinSetByKey<A, B, F, K2> {
+        InnerJoinSetByKey { a: self.a.clone(), b: self.b.clone(), key: self.key.clone() }
+    }
+}
+
+pub struct OuterJoinByKey<A: Iterator, B: Iterator, F, K2> {
+    left: Keyed<A, K2>,
+    right: Keyed<B, K2>,
+    key: F,
+}
+
+impl<A, B, F, K2> Clone for OuterJoinByKey<A, B, F, K2>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, F: Clone, K2: Clone {
+    fn clone(&self) -> OuterJoinByKey<A, B, F, K2> {
+        OuterJoinByKey { left: self.left.clone(), right: self.right.clone(), key: self.key.clone() }
+    }
+}
+
+pub struct UnionByKey<A: Iterator, B: Iterator, F, K2> {
+    a: Keyed<A, K2>,
+    b: Keyed<B, K2>,
+    key: F,
+}
+
+impl<A, B, F, K2> Clone for UnionByKey<A, B, F, K2>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, F: Clone, K2: Clone {
+    fn clone(&self) -> UnionByKey<A, B, F, K2> {
+        UnionByKey { a: self.a.clone(), b: self.b.clone(), key: self.key.clone() }
+    }
+}
+
+pub struct DifferenceByKey<A: Iterator, B: Iterator, F, K2> {
+    a: Keyed<A, K2>,
+    b: Keyed<B, K2>,
+    key: F,
+}
+
+impl<A, B, F, K2> Clone for DifferenceByKey<A, B, F, K2>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, F: Clone, K2: Clone {
+    fn clone(&self) -> DifferenceByKey<A, B, F, K2> {
+        DifferenceByKey { a: self.a.clone(), b: self.b.clone(), key: self.key.clone() }
+    }
+}
+
+pub struct SymmetricDifferenceByKey<A: Iterator, B: Iterator, F, K2> {
+    a: Keyed<A, K2>,
+    b: Keyed<B, K2>,
+    key: F,
+}
+
+impl<A, B, F, K2> Clone for SymmetricDifferenceByKey<A, B, F, K2>
+where A: Clone + Iterator, B: Clone + Iterator, A::Item: Clone, B::Item: Clone, F: Clone, K2: Clone {
+    fn clone(&self) -> SymmetricDifferenceByKey<A, B, F, K2> {
+        SymmetricDifferenceByKey { a: self.a.clone(), b: self.b.clone(), key: self.key.clone() }
+    }
+}
+
 impl<A, B> Iterator for InnerJoinMap<A, B>
 where A: OrderedMapIterator,
       B: OrderedMapIterator<Key=A::Key>,
@@ -139,8 +605,23 @@ where A: OrderedMapIterator,
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            _ => None
+        };
+        (0, upper)
+    }
 }
 
+impl<A, B> FusedIterator for InnerJoinMap<A, B>
+where A: OrderedMapIterator + FusedIterator,
+      B: OrderedMapIterator<Key=A::Key> + FusedIterator,
+      A::Key: Ord,
+{}
 
 impl<A, B> Iterator for InnerJoinSet<A, B>
 where A: OrderedSetIterator,
@@ -179,8 +660,24 @@ where A: OrderedSetIterator,
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            _ => None
+        };
+        (0, upper)
+    }
 }
 
+impl<A, B> FusedIterator for InnerJoinSet<A, B>
+where A: OrderedSetIterator + FusedIterator,
+      B: OrderedSetIterator<Item=A::Item> + FusedIterator,
+      A::Item: Ord,
+{}
+
 impl<MapIter, SetIter> Iterator for InnerJoinMapSet<MapIter, SetIter>
 where SetIter: OrderedSetIterator,
       MapIter: OrderedMapIterator<Key=SetIter::Item>,
@@ -221,8 +718,24 @@ where SetIter: OrderedSetIterator,
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, set_upper) = self.set.size_hint();
+        let (_, map_upper) = self.map.size_hint();
+        let upper = match (set_upper, map_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            _ => None
+        };
+        (0, upper)
+    }
 }
 
+impl<MapIter, SetIter> FusedIterator for InnerJoinMapSet<MapIter, SetIter>
+where SetIter: OrderedSetIterator + FusedIterator,
+      MapIter: OrderedMapIterator<Key=SetIter::Item> + FusedIterator,
+      MapIter::Key: Ord,
+{}
+
 impl<A, B> Iterator for OuterJoin<A, B>
 where A: OrderedMapIterator,
       B: OrderedMapIterator<Key=A::Key>,
@@ -257,65 +770,982 @@ where A: OrderedMapIterator,
             }
         }
     }
-}
 
-impl<'a, K: Ord> OrderedSetIterator for btree_set::Iter<'a, K> {}
-impl<'a, K: Ord, V> OrderedMapIterator for btree_map::Iter<'a, K, V> {
-    type Key = &'a K;
-    type Val = &'a V;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left_lower, left_upper) = self.left.size_hint();
+        let (right_lower, right_upper) = self.right.size_hint();
+        let upper = match (left_upper, right_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (left_lower.max(right_lower), upper)
+    }
 }
 
-impl<K: Ord, V> OrderedMapIterator for btree_map::IntoIter<K, V> {
-    type Key = K;
-    type Val = V;
-}
+impl<A, B> FusedIterator for OuterJoin<A, B>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      A::Key: Ord + Eq,
+{}
 
-impl<'a, K: Ord, V> OrderedMapIterator for btree_map::IterMut<'a, K, V> {
-    type Key = &'a K;
-    type Val = &'a mut V;
-}
+impl<A, B> Iterator for Union<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
+{
+    type Item = A::Item;
 
-impl<'a, K: Ord, V> OrderedSetIterator for btree_map::Keys<'a, K, V> {}
+    fn next(&mut self) -> Option<A::Item> {
+        let which = match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Less,
+            (None, Some(_)) => Greater,
+            (None, None) => return None
+        };
 
-impl<'a, V> OrderedMapIterator for vec_map::Iter<'a, V> {
-    type Key = usize;
-    type Val = &'a V;
+        match which {
+            Less => self.a.next(),
+            Equal => { self.b.next(); self.a.next() },
+            Greater => self.b.next()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (a_lower.max(b_lower), upper)
+    }
 }
 
-impl<'a, B: bit_vec::BitBlock> OrderedSetIterator for bit_set::Iter<'a, B> {}
+impl<A, B> FusedIterator for Union<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
+{}
 
-impl<A, B> OrderedMapIterator for InnerJoinMap<A, B>
-where A: OrderedMapIterator,
-      B: OrderedMapIterator<Key=A::Key>,
-      A::Key: Ord,
+impl<A, B> Iterator for Difference<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
 {
-    type Key = A::Key;
-    type Val = (A::Val, B::Val);
-}
+    type Item = A::Item;
 
-impl<A, B> OrderedMapIterator for InnerJoinMapSet<A, B>
-where A: OrderedMapIterator,
-      B: OrderedSetIterator<Item=A::Key>,
-      A::Key: Ord,
-{
-    type Key = A::Key;
-    type Val = A::Val;
+    fn next(&mut self) -> Option<A::Item> {
+        loop {
+            let which = match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => return self.a.next(),
+                (None, _) => return None
+            };
+
+            match which {
+                Less => return self.a.next(),
+                Equal => { self.a.next(); self.b.next(); },
+                Greater => { self.b.next(); }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        (0, a_upper)
+    }
 }
 
-impl<A, B> OrderedSetIterator for InnerJoinSet<A, B>
+impl<A, B> FusedIterator for Difference<A, B>
 where A: OrderedSetIterator,
       B: OrderedSetIterator<Item=A::Item>,
       A::Item: Ord,
 {}
 
-impl<A, B> OrderedMapIterator for OuterJoin<A, B>
-where A: OrderedMapIterator,
-      B: OrderedMapIterator<Key=A::Key>,
-      A::Key: Ord,
+impl<A, B> Iterator for SymmetricDifference<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
 {
-    type Key = A::Key;
-    type Val = (Option<A::Val>, Option<B::Val>);
-}
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        loop {
+            let which = match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => return self.a.next(),
+                (None, Some(_)) => return self.b.next(),
+                (None, None) => return None
+            };
+
+            match which {
+                Less => return self.a.next(),
+                Equal => { self.a.next(); self.b.next(); },
+                Greater => return self.b.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (0, upper)
+    }
+}
+
+impl<A, B> FusedIterator for SymmetricDifference<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
+{}
+
+impl<A, B> OrderedSetIterator for Union<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
+{}
+
+impl<A, B> OrderedSetIterator for Difference<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
+{}
+
+impl<A, B> OrderedSetIterator for SymmetricDifference<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
+{}
+
+impl<A, B> Iterator for Diff<A, B>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key, Val=A::Val>,
+      A::Key: Ord,
+      A::Val: PartialEq,
+{
+    type Item = DiffItem<A::Key, A::Val>;
+
+    fn next(&mut self) -> Option<DiffItem<A::Key, A::Val>> {
+        loop {
+            let which = match (self.a.peek(), self.b.peek()) {
+                (Some((ka, _)), Some((kb, _))) => ka.cmp(kb),
+                (Some(_), None) => Less,
+                (None, Some(_)) => Greater,
+                (None, None) => return None
+            };
+
+            match which {
+                Less => {
+                    let (k, v) = self.a.next().expect("no value found");
+                    return Some(DiffItem::Removed(k, v));
+                },
+                Greater => {
+                    let (k, v) = self.b.next().expect("no value found");
+                    return Some(DiffItem::Added(k, v));
+                },
+                Equal => {
+                    let (key, old) = self.a.next().expect("no value found");
+                    let (_, new) = self.b.next().expect("no value found");
+
+                    if old != new {
+                        return Some(DiffItem::Update { key, old, new });
+                    }
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (0, upper)
+    }
+}
+
+impl<A, B> FusedIterator for Diff<A, B>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key, Val=A::Val>,
+      A::Key: Ord,
+      A::Val: PartialEq,
+{}
+
+impl<A, B, C> Iterator for InnerJoinMapBy<A, B, C>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      C: FnMut(&A::Key, &A::Key) -> Ordering,
+{
+    type Item = (A::Key, (A::Val, B::Val));
+
+    fn next(&mut self) -> Option<(A::Key, (A::Val, B::Val))> {
+        let (mut key_a, mut data_a) = match self.a.next() {
+            None => return None,
+            Some((key, data)) => (key, data)
+        };
+
+        let (mut key_b, mut data_b) = match self.b.next() {
+            None => return None,
+            Some((key, data)) => (key, data)
+        };
+
+        loop {
+            match (self.cmp)(&key_a, &key_b) {
+                Less => {
+                    match self.a.next() {
+                        None => return None,
+                        Some((key, data)) => {
+                            key_a = key;
+                            data_a = data;
+                        }
+                    };
+                },
+                Equal => return Some((key_a, (data_a, data_b))),
+                Greater => {
+                    match self.b.next() {
+                        None => return None,
+                        Some((key, data)) => {
+                            key_b = key;
+                            data_b = data;
+                        }
+                    };
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            _ => None
+        };
+        (0, upper)
+    }
+}
+
+impl<A, B, C> FusedIterator for InnerJoinMapBy<A, B, C>
+where A: OrderedMapIterator + FusedIterator,
+      B: OrderedMapIterator<Key=A::Key> + FusedIterator,
+      C: FnMut(&A::Key, &A::Key) -> Ordering,
+{}
+
+impl<A, B, C> Iterator for InnerJoinSetBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        let mut key_a = self.a.next()?;
+        let mut key_b = self.b.next()?;
+
+        loop {
+            match (self.cmp)(&key_a, &key_b) {
+                Less => { key_a = self.a.next()?; },
+                Equal => return Some(key_a),
+                Greater => { key_b = self.b.next()?; }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            _ => None
+        };
+        (0, upper)
+    }
+}
+
+impl<A, B, C> FusedIterator for InnerJoinSetBy<A, B, C>
+where A: OrderedSetIterator + FusedIterator,
+      B: OrderedSetIterator<Item=A::Item> + FusedIterator,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{}
+
+impl<A, B, C> Iterator for OuterJoinBy<A, B, C>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      C: FnMut(&A::Key, &A::Key) -> Ordering,
+{
+    type Item = (A::Key, (Option<A::Val>, Option<B::Val>));
+
+    fn next(&mut self) -> Option<(A::Key, (Option<A::Val>, Option<B::Val>))> {
+        let which = match (self.left.peek(), self.right.peek()) {
+            (Some((ka, _)), Some((kb, _))) => (self.cmp)(kb, ka),
+            (None, Some(_)) => Less,
+            (Some(_), None) => Greater,
+            (None, None) => return None
+        };
+
+        match which {
+            Equal => {
+                let ((k, a), (_, b)) =
+                    (self.left.next().expect("no value found"),
+                     self.right.next().expect("no value found"));
+
+                Some((k, (Some(a), Some(b))))
+            }
+            Less => {
+                let (k, v) = self.right.next().expect("no value found");
+                Some((k, (None, Some(v))))
+            }
+            Greater => {
+                let (k, v) = self.left.next().expect("no value found");
+                Some((k, (Some(v), None)))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left_lower, left_upper) = self.left.size_hint();
+        let (right_lower, right_upper) = self.right.size_hint();
+        let upper = match (left_upper, right_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (left_lower.max(right_lower), upper)
+    }
+}
+
+impl<A, B, C> FusedIterator for OuterJoinBy<A, B, C>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      C: FnMut(&A::Key, &A::Key) -> Ordering,
+{}
+
+impl<A, B, C> Iterator for UnionBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        let which = match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) => (self.cmp)(a, b),
+            (Some(_), None) => Less,
+            (None, Some(_)) => Greater,
+            (None, None) => return None
+        };
+
+        match which {
+            Less => self.a.next(),
+            Equal => { self.b.next(); self.a.next() },
+            Greater => self.b.next()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (a_lower.max(b_lower), upper)
+    }
+}
+
+impl<A, B, C> FusedIterator for UnionBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{}
+
+impl<A, B, C> Iterator for DifferenceBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        loop {
+            let which = match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) => (self.cmp)(a, b),
+                (Some(_), None) => return self.a.next(),
+                (None, _) => return None
+            };
+
+            match which {
+                Less => return self.a.next(),
+                Equal => { self.a.next(); self.b.next(); },
+                Greater => { self.b.next(); }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        (0, a_upper)
+    }
+}
+
+impl<A, B, C> FusedIterator for DifferenceBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{}
+
+impl<A, B, C> Iterator for SymmetricDifferenceBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        loop {
+            let which = match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) => (self.cmp)(a, b),
+                (Some(_), None) => return self.a.next(),
+                (None, Some(_)) => return self.b.next(),
+                (None, None) => return None
+            };
+
+            match which {
+                Less => return self.a.next(),
+                Equal => { self.a.next(); self.b.next(); },
+                Greater => return self.b.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (0, upper)
+    }
+}
+
+impl<A, B, C> FusedIterator for SymmetricDifferenceBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{}
+
+impl<A, B, C> OrderedMapIterator for InnerJoinMapBy<A, B, C>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      C: FnMut(&A::Key, &A::Key) -> Ordering,
+{
+    type Key = A::Key;
+    type Val = (A::Val, B::Val);
+}
+
+impl<A, B, C> OrderedMapIterator for OuterJoinBy<A, B, C>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      C: FnMut(&A::Key, &A::Key) -> Ordering,
+{
+    type Key = A::Key;
+    type Val = (Option<A::Val>, Option<B::Val>);
+}
+
+impl<A, B, C> OrderedSetIterator for InnerJoinSetBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{}
+
+impl<A, B, C> OrderedSetIterator for UnionBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{}
+
+impl<A, B, C> OrderedSetIterator for DifferenceBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{}
+
+impl<A, B, C> OrderedSetIterator for SymmetricDifferenceBy<A, B, C>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      C: FnMut(&A::Item, &A::Item) -> Ordering,
+{}
+
+impl<A, B, F, K2> Iterator for InnerJoinMapByKey<A, B, F, K2>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      F: FnMut(&A::Key) -> K2,
+      K2: Ord,
+{
+    type Item = (A::Key, (A::Val, B::Val));
+
+    fn next(&mut self) -> Option<(A::Key, (A::Val, B::Val))> {
+        let key = &mut self.key;
+
+        loop {
+            let which = match (self.a.peek_key(&mut |item: &(A::Key, A::Val)| key(&item.0)),
+                               self.b.peek_key(&mut |item: &(A::Key, B::Val)| key(&item.0))) {
+                (Some(ka), Some(kb)) => ka.cmp(kb),
+                _ => return None
+            };
+
+            match which {
+                Less => { self.a.next(); },
+                Equal => {
+                    let (key, data_a) = self.a.next().expect("no value found");
+                    let (_, data_b) = self.b.next().expect("no value found");
+                    return Some((key, (data_a, data_b)));
+                },
+                Greater => { self.b.next(); }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            _ => None
+        };
+        (0, upper)
+    }
+}
+
+impl<A, B, F, K2> FusedIterator for InnerJoinMapByKey<A, B, F, K2>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      F: FnMut(&A::Key) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> OrderedMapIterator for InnerJoinMapByKey<A, B, F, K2>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      F: FnMut(&A::Key) -> K2,
+      K2: Ord,
+{
+    type Key = A::Key;
+    type Val = (A::Val, B::Val);
+}
+
+impl<A, B, F, K2> Iterator for InnerJoinSetByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        loop {
+            let which = match (self.a.peek_key(&mut self.key), self.b.peek_key(&mut self.key)) {
+                (Some(ka), Some(kb)) => ka.cmp(kb),
+                _ => return None
+            };
+
+            match which {
+                Less => { self.a.next(); },
+                Equal => {
+                    let item = self.a.next().expect("no value found");
+                    self.b.next();
+                    return Some(item);
+                },
+                Greater => { self.b.next(); }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            _ => None
+        };
+        (0, upper)
+    }
+}
+
+impl<A, B, F, K2> FusedIterator for InnerJoinSetByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> OrderedSetIterator for InnerJoinSetByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> Iterator for OuterJoinByKey<A, B, F, K2>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      F: FnMut(&A::Key) -> K2,
+      K2: Ord,
+{
+    type Item = (A::Key, (Option<A::Val>, Option<B::Val>));
+
+    fn next(&mut self) -> Option<(A::Key, (Option<A::Val>, Option<B::Val>))> {
+        let key = &mut self.key;
+        let which = match (self.left.peek_key(&mut |item: &(A::Key, A::Val)| key(&item.0)),
+                           self.right.peek_key(&mut |item: &(A::Key, B::Val)| key(&item.0))) {
+            (Some(ka), Some(kb)) => kb.cmp(ka),
+            (None, Some(_)) => Less,
+            (Some(_), None) => Greater,
+            (None, None) => return None
+        };
+
+        match which {
+            Equal => {
+                let ((k, a), (_, b)) =
+                    (self.left.next().expect("no value found"),
+                     self.right.next().expect("no value found"));
+
+                Some((k, (Some(a), Some(b))))
+            }
+            Less => {
+                let (k, v) = self.right.next().expect("no value found");
+                Some((k, (None, Some(v))))
+            }
+            Greater => {
+                let (k, v) = self.left.next().expect("no value found");
+                Some((k, (Some(v), None)))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (left_lower, left_upper) = self.left.size_hint();
+        let (right_lower, right_upper) = self.right.size_hint();
+        let upper = match (left_upper, right_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (left_lower.max(right_lower), upper)
+    }
+}
+
+impl<A, B, F, K2> FusedIterator for OuterJoinByKey<A, B, F, K2>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      F: FnMut(&A::Key) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> OrderedMapIterator for OuterJoinByKey<A, B, F, K2>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      F: FnMut(&A::Key) -> K2,
+      K2: Ord,
+{
+    type Key = A::Key;
+    type Val = (Option<A::Val>, Option<B::Val>);
+}
+
+impl<A, B, F, K2> Iterator for UnionByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        let which = match (self.a.peek_key(&mut self.key), self.b.peek_key(&mut self.key)) {
+            (Some(ka), Some(kb)) => ka.cmp(kb),
+            (Some(_), None) => Less,
+            (None, Some(_)) => Greater,
+            (None, None) => return None
+        };
+
+        match which {
+            Less => self.a.next(),
+            Equal => { self.b.next(); self.a.next() },
+            Greater => self.b.next()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (a_lower.max(b_lower), upper)
+    }
+}
+
+impl<A, B, F, K2> FusedIterator for UnionByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> OrderedSetIterator for UnionByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> Iterator for DifferenceByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        loop {
+            let which = match (self.a.peek_key(&mut self.key), self.b.peek_key(&mut self.key)) {
+                (Some(ka), Some(kb)) => ka.cmp(kb),
+                (Some(_), None) => return self.a.next(),
+                (None, _) => return None
+            };
+
+            match which {
+                Less => return self.a.next(),
+                Equal => { self.a.next(); self.b.next(); },
+                Greater => { self.b.next(); }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        (0, a_upper)
+    }
+}
+
+impl<A, B, F, K2> FusedIterator for DifferenceByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> OrderedSetIterator for DifferenceByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> Iterator for SymmetricDifferenceByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        loop {
+            let which = match (self.a.peek_key(&mut self.key), self.b.peek_key(&mut self.key)) {
+                (Some(ka), Some(kb)) => ka.cmp(kb),
+                (Some(_), None) => return self.a.next(),
+                (None, Some(_)) => return self.b.next(),
+                (None, None) => return None
+            };
+
+            match which {
+                Less => return self.a.next(),
+                Equal => { self.a.next(); self.b.next(); },
+                Greater => return self.b.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, a_upper) = self.a.size_hint();
+        let (_, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            _ => None
+        };
+        (0, upper)
+    }
+}
+
+impl<A, B, F, K2> FusedIterator for SymmetricDifferenceByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{}
+
+impl<A, B, F, K2> OrderedSetIterator for SymmetricDifferenceByKey<A, B, F, K2>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      F: FnMut(&A::Item) -> K2,
+      K2: Ord,
+{}
+
+impl<'a, K: Ord> OrderedSetIterator for btree_set::Iter<'a, K> {}
+impl<'a, K: Ord, V> OrderedMapIterator for btree_map::Iter<'a, K, V> {
+    type Key = &'a K;
+    type Val = &'a V;
+}
+
+impl<K: Ord, V> OrderedMapIterator for btree_map::IntoIter<K, V> {
+    type Key = K;
+    type Val = V;
+}
+
+impl<'a, K: Ord, V> OrderedMapIterator for btree_map::IterMut<'a, K, V> {
+    type Key = &'a K;
+    type Val = &'a mut V;
+}
+
+impl<'a, K: Ord, V> OrderedSetIterator for btree_map::Keys<'a, K, V> {}
+
+impl<'a, V> OrderedMapIterator for vec_map::Iter<'a, V> {
+    type Key = usize;
+    type Val = &'a V;
+}
+
+impl<'a, B: bit_vec::BitBlock> OrderedSetIterator for bit_set::Iter<'a, B> {}
+
+/// A wrapper that asserts an iterator over key-value pairs is already
+/// sorted by key, so it can be used with [`OrderedMapIterator`]'s joins.
+///
+/// Constructed with [`SortedByKey::assume_sorted_by_key`]. The caller is
+/// responsible for the ordering invariant; this type performs no checks.
+#[derive(Clone)]
+pub struct AssumeSortedByKey<I> {
+    iter: I
+}
+
+impl<I, K, V> Iterator for AssumeSortedByKey<I>
+where I: Iterator<Item=(K, V)> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: FusedIterator, K, V> FusedIterator for AssumeSortedByKey<I>
+where I: Iterator<Item=(K, V)> {}
+
+impl<I, K, V> OrderedMapIterator for AssumeSortedByKey<I>
+where I: Iterator<Item=(K, V)> {
+    type Key = K;
+    type Val = V;
+}
+
+/// Extension trait adding [`assume_sorted_by_key`](SortedByKey::assume_sorted_by_key)
+/// to any iterator over key-value pairs.
+pub trait SortedByKey<K, V>: Iterator<Item=(K, V)> + Sized {
+    /// Asserts that this iterator is already sorted by key, so it can be
+    /// joined with other [`OrderedMapIterator`]s.
+    ///
+    /// It is the caller's responsibility to ensure the iterator really is
+    /// sorted; violating this invariant will produce incorrect join
+    /// results without panicking.
+    fn assume_sorted_by_key(self) -> AssumeSortedByKey<Self> {
+        AssumeSortedByKey { iter: self }
+    }
+}
+
+impl<I, K, V> SortedByKey<K, V> for I where I: Iterator<Item=(K, V)> {}
+
+/// A wrapper that asserts an iterator is already sorted, so it can be used
+/// with [`OrderedSetIterator`]'s joins and set operations.
+///
+/// Constructed with [`Sorted::assume_sorted`]. The caller is responsible
+/// for the ordering invariant; this type performs no checks.
+#[derive(Clone)]
+pub struct AssumeSorted<I> {
+    iter: I
+}
+
+impl<I: Iterator> Iterator for AssumeSorted<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: FusedIterator> FusedIterator for AssumeSorted<I> {}
+
+impl<I: Iterator> OrderedSetIterator for AssumeSorted<I> {}
+
+/// Extension trait adding [`assume_sorted`](Sorted::assume_sorted) to any
+/// iterator.
+pub trait Sorted: Iterator + Sized {
+    /// Asserts that this iterator is already sorted, so it can be joined
+    /// with other [`OrderedSetIterator`]s.
+    ///
+    /// It is the caller's responsibility to ensure the iterator really is
+    /// sorted; violating this invariant will produce incorrect join
+    /// results without panicking.
+    fn assume_sorted(self) -> AssumeSorted<Self> {
+        AssumeSorted { iter: self }
+    }
+}
+
+impl<I: Iterator> Sorted for I {}
+
+impl<A, B> OrderedMapIterator for InnerJoinMap<A, B>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      A::Key: Ord,
+{
+    type Key = A::Key;
+    type Val = (A::Val, B::Val);
+}
+
+impl<A, B> OrderedMapIterator for InnerJoinMapSet<A, B>
+where A: OrderedMapIterator,
+      B: OrderedSetIterator<Item=A::Key>,
+      A::Key: Ord,
+{
+    type Key = A::Key;
+    type Val = A::Val;
+}
+
+impl<A, B> OrderedSetIterator for InnerJoinSet<A, B>
+where A: OrderedSetIterator,
+      B: OrderedSetIterator<Item=A::Item>,
+      A::Item: Ord,
+{}
+
+impl<A, B> OrderedMapIterator for OuterJoin<A, B>
+where A: OrderedMapIterator,
+      B: OrderedMapIterator<Key=A::Key>,
+      A::Key: Ord,
+{
+    type Key = A::Key;
+    type Val = (Option<A::Val>, Option<B::Val>);
+}
 
 
 #[cfg(test)]
@@ -363,6 +1793,60 @@ mod tests {
         assert_eq!(expected, powers_of_two_and_three);
     }
 
+    #[test]
+    fn union_two_sets() {
+        use std::collections::BTreeSet;
+
+        let powers_of_two: BTreeSet<i32> = (1..6).map(|x| x * 2).collect();
+        let powers_of_three: BTreeSet<i32> = (1..6).map(|x| x * 3).collect();
+
+        let expected = vec![2, 3, 4, 6, 8, 9, 10, 12, 15];
+
+        let merged: Vec<i32> =
+            powers_of_two.iter()
+            .union(powers_of_three.iter())
+            .map(|&x| x)
+            .collect();
+
+        assert_eq!(expected, merged);
+    }
+
+    #[test]
+    fn difference_two_sets() {
+        use std::collections::BTreeSet;
+
+        let powers_of_two: BTreeSet<i32> = (1..6).map(|x| x * 2).collect();
+        let powers_of_three: BTreeSet<i32> = (1..6).map(|x| x * 3).collect();
+
+        let expected = vec![2, 4, 8, 10];
+
+        let diff: Vec<i32> =
+            powers_of_two.iter()
+            .difference(powers_of_three.iter())
+            .map(|&x| x)
+            .collect();
+
+        assert_eq!(expected, diff);
+    }
+
+    #[test]
+    fn symmetric_difference_two_sets() {
+        use std::collections::BTreeSet;
+
+        let powers_of_two: BTreeSet<i32> = (1..6).map(|x| x * 2).collect();
+        let powers_of_three: BTreeSet<i32> = (1..6).map(|x| x * 3).collect();
+
+        let expected = vec![2, 3, 4, 8, 9, 10, 12, 15];
+
+        let sym_diff: Vec<i32> =
+            powers_of_two.iter()
+            .symmetric_difference(powers_of_three.iter())
+            .map(|&x| x)
+            .collect();
+
+        assert_eq!(expected, sym_diff);
+    }
+
     #[test]
     fn join_two_maps() {
         use std::collections::BTreeMap;
@@ -397,6 +1881,99 @@ mod tests {
         assert_eq!(None, powers_of_two_and_three.next());
     }
 
+    #[test]
+    fn diff_two_maps() {
+        use std::collections::BTreeMap;
+        use super::DiffItem;
+
+        let mut before = BTreeMap::new();
+        before.insert(1, "a");
+        before.insert(2, "b");
+        before.insert(3, "c");
+
+        let mut after = BTreeMap::new();
+        after.insert(2, "b");
+        after.insert(3, "changed");
+        after.insert(4, "d");
+
+        let changes: Vec<DiffItem<i32, &str>> =
+            before.into_iter()
+            .diff(after.into_iter())
+            .collect();
+
+        assert_eq!(vec![
+            DiffItem::Removed(1, "a"),
+            DiffItem::Update { key: 3, old: "c", new: "changed" },
+            DiffItem::Added(4, "d"),
+        ], changes);
+    }
+
+    #[test]
+    fn inner_join_set_by_key_case_insensitive() {
+        use std::collections::BTreeSet;
+
+        let words: BTreeSet<String> = vec!["Apple", "Banana", "Cherry"]
+            .into_iter().map(|s| s.to_string()).collect();
+        let other: BTreeSet<String> = vec!["APPLE", "CHERRY", "DATE"]
+            .into_iter().map(|s| s.to_string()).collect();
+
+        let matched: Vec<String> =
+            words.iter()
+            .inner_join_set_by_key(other.iter(), |s| s.to_lowercase())
+            .map(|s| s.clone())
+            .collect();
+
+        assert_eq!(vec!["Apple".to_string(), "Cherry".to_string()], matched);
+    }
+
+    #[test]
+    fn union_by_key_ignores_second_field() {
+        use std::collections::BTreeSet;
+
+        let a: BTreeSet<(i32, &str)> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+        let b: BTreeSet<(i32, &str)> = vec![(2, "x"), (3, "y"), (4, "z")].into_iter().collect();
+
+        let merged: Vec<(i32, &str)> =
+            a.iter()
+            .union_by_key(b.iter(), |&&(id, _)| id)
+            .map(|&pair| pair)
+            .collect();
+
+        assert_eq!(vec![(1, "a"), (2, "b"), (3, "c"), (4, "z")], merged);
+    }
+
+    #[test]
+    fn join_sets_assumed_sorted() {
+        use super::Sorted;
+
+        let powers_of_two = vec![2, 4, 6, 8, 10, 12];
+        let powers_of_three = vec![3, 6, 9, 12, 15];
+
+        let expected = vec![6, 12];
+
+        let joined: Vec<i32> =
+            powers_of_two.into_iter().assume_sorted()
+            .inner_join_set(powers_of_three.into_iter().assume_sorted())
+            .collect();
+
+        assert_eq!(expected, joined);
+    }
+
+    #[test]
+    fn join_maps_assumed_sorted_by_key() {
+        use super::SortedByKey;
+
+        let powers_of_two = vec![(2, 1), (4, 2), (6, 3)];
+        let powers_of_three = vec![(3, 1), (6, 2), (9, 3)];
+
+        let mut joined =
+            powers_of_two.into_iter().assume_sorted_by_key()
+            .inner_join_map(powers_of_three.into_iter().assume_sorted_by_key());
+
+        assert_eq!(Some((6, (3, 2))), joined.next());
+        assert_eq!(None, joined.next());
+    }
+
     #[test]
     fn outer_join_fizz_buzz() {
         use std::collections::BTreeMap;
@@ -430,6 +2007,261 @@ mod tests {
         }
     }
 
+    #[test]
+    fn inner_join_map_by_reverse_order() {
+        use super::SortedByKey;
+
+        let a = vec![(6, "six"), (4, "four"), (2, "two")];
+        let b = vec![(6, 3), (3, 1), (2, 1)];
+
+        let matched: Vec<(i32, &str, i32)> =
+            a.into_iter().assume_sorted_by_key()
+            .inner_join_map_by(b.into_iter().assume_sorted_by_key(), |ka, kb| kb.cmp(ka))
+            .map(|(k, (name, count))| (k, name, count))
+            .collect();
+
+        assert_eq!(vec![(6, "six", 3), (2, "two", 1)], matched);
+    }
+
+    #[test]
+    fn outer_join_by_reverse_order() {
+        use super::SortedByKey;
+
+        let a = vec![(2, "b"), (1, "a")];
+        let b = vec![(3, "y"), (2, "x")];
+
+        let joined: Vec<(i32, (Option<&str>, Option<&str>))> =
+            a.into_iter().assume_sorted_by_key()
+            .outer_join_by(b.into_iter().assume_sorted_by_key(), |ka, kb| kb.cmp(ka))
+            .collect();
+
+        assert_eq!(vec![
+            (3, (None, Some("y"))),
+            (2, (Some("b"), Some("x"))),
+            (1, (Some("a"), None)),
+        ], joined);
+    }
+
+    #[test]
+    fn inner_join_set_by_reverse_order() {
+        use super::Sorted;
+
+        let a = vec![6, 4, 2];
+        let b = vec![6, 3, 2];
+
+        let matched: Vec<i32> =
+            a.into_iter().assume_sorted()
+            .inner_join_set_by(b.into_iter().assume_sorted(), |ka, kb| kb.cmp(ka))
+            .collect();
+
+        assert_eq!(vec![6, 2], matched);
+    }
+
+    #[test]
+    fn union_by_reverse_order() {
+        use super::Sorted;
+
+        let a = vec![4, 2, 1];
+        let b = vec![4, 3, 2];
+
+        let merged: Vec<i32> =
+            a.into_iter().assume_sorted()
+            .union_by(b.into_iter().assume_sorted(), |ka, kb| kb.cmp(ka))
+            .collect();
+
+        assert_eq!(vec![4, 3, 2, 1], merged);
+    }
+
+    #[test]
+    fn difference_by_reverse_order() {
+        use super::Sorted;
+
+        let a = vec![4, 2, 1];
+        let b = vec![3, 2];
+
+        let diff: Vec<i32> =
+            a.into_iter().assume_sorted()
+            .difference_by(b.into_iter().assume_sorted(), |ka, kb| kb.cmp(ka))
+            .collect();
+
+        assert_eq!(vec![4, 1], diff);
+    }
+
+    #[test]
+    fn symmetric_difference_by_reverse_order() {
+        use super::Sorted;
+
+        let a = vec![4, 2, 1];
+        let b = vec![3, 2];
+
+        let sym_diff: Vec<i32> =
+            a.into_iter().assume_sorted()
+            .symmetric_difference_by(b.into_iter().assume_sorted(), |ka, kb| kb.cmp(ka))
+            .collect();
+
+        assert_eq!(vec![4, 3, 1], sym_diff);
+    }
+
+    #[test]
+    fn inner_join_map_size_hint_after_partial_consumption() {
+        use std::collections::BTreeMap;
+
+        let a: BTreeMap<i32, i32> = (1..10).map(|x| (x * 2, x)).collect();
+        let b: BTreeMap<i32, i32> = (1..10).map(|x| (x * 3, x)).collect();
+
+        let mut joined = a.iter().inner_join_map(b.iter());
+        joined.next();
+
+        let (lo, hi) = joined.size_hint();
+        let count = joined.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn inner_join_set_size_hint_after_partial_consumption() {
+        use std::collections::BTreeSet;
+
+        let a: BTreeSet<i32> = (1..10).map(|x| x * 2).collect();
+        let b: BTreeSet<i32> = (1..10).map(|x| x * 3).collect();
+
+        let mut joined = a.iter().inner_join_set(b.iter());
+        joined.next();
+
+        let (lo, hi) = joined.size_hint();
+        let count = joined.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn inner_join_map_set_size_hint_after_partial_consumption() {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        let a: BTreeSet<i32> = (1..10).map(|x| x * 2).collect();
+        let b: BTreeMap<i32, i32> = (1..10).map(|x| (x * 3, x)).collect();
+
+        let mut joined = a.iter().inner_join_map(b.iter());
+        joined.next();
+
+        let (lo, hi) = joined.size_hint();
+        let count = joined.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn outer_join_size_hint_after_partial_consumption() {
+        use std::collections::BTreeMap;
+
+        let a: BTreeMap<i32, i32> = (1..10).map(|x| (x * 2, x)).collect();
+        let b: BTreeMap<i32, i32> = (1..10).map(|x| (x * 3, x)).collect();
+
+        let mut joined = a.iter().outer_join(b.iter());
+        joined.next();
+
+        let (lo, hi) = joined.size_hint();
+        let count = joined.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn inner_join_map_by_key_size_hint_after_partial_consumption() {
+        use std::collections::BTreeMap;
+
+        let a: BTreeMap<i32, i32> = (1..10).map(|x| (x * 2, x)).collect();
+        let b: BTreeMap<i32, i32> = (1..10).map(|x| (x * 3, x)).collect();
+
+        let mut joined = a.iter().inner_join_map_by_key(b.iter(), |&k| k);
+        joined.next();
+
+        let (lo, hi) = joined.size_hint();
+        let count = joined.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn inner_join_set_by_key_size_hint_after_partial_consumption() {
+        use std::collections::BTreeSet;
+
+        let a: BTreeSet<i32> = (1..10).map(|x| x * 2).collect();
+        let b: BTreeSet<i32> = (1..10).map(|x| x * 3).collect();
+
+        let mut joined = a.iter().inner_join_set_by_key(b.iter(), |&&k| k);
+        joined.next();
+
+        let (lo, hi) = joined.size_hint();
+        let count = joined.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn outer_join_by_key_size_hint_after_partial_consumption() {
+        use std::collections::BTreeMap;
+
+        let a: BTreeMap<i32, i32> = vec![(1, 1), (3, 3)].into_iter().collect();
+        let b: BTreeMap<i32, i32> = vec![(2, 2), (4, 4)].into_iter().collect();
+
+        let mut joined = a.iter().outer_join_by_key(b.iter(), |&k| k);
+        joined.next();
+
+        let (lo, hi) = joined.size_hint();
+        let count = joined.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn union_by_key_size_hint_after_partial_consumption() {
+        use std::collections::BTreeSet;
+
+        let a: BTreeSet<(i32, &str)> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+        let b: BTreeSet<(i32, &str)> = vec![(2, "x"), (3, "y"), (4, "z")].into_iter().collect();
+
+        let mut merged = a.iter().union_by_key(b.iter(), |&&(id, _)| id);
+        merged.next();
+
+        let (lo, hi) = merged.size_hint();
+        let count = merged.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn difference_by_key_size_hint_after_partial_consumption() {
+        use std::collections::BTreeSet;
+
+        let a: BTreeSet<(i32, &str)> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+        let b: BTreeSet<(i32, &str)> = vec![(2, "x"), (3, "y")].into_iter().collect();
+
+        let mut diff = a.iter().difference_by_key(b.iter(), |&&(id, _)| id);
+        diff.next();
+
+        let (lo, hi) = diff.size_hint();
+        let count = diff.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
+    #[test]
+    fn symmetric_difference_by_key_size_hint_after_partial_consumption() {
+        use std::collections::BTreeSet;
+
+        let a: BTreeSet<(i32, &str)> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+        let b: BTreeSet<(i32, &str)> = vec![(2, "x"), (3, "y"), (4, "z")].into_iter().collect();
+
+        let mut sym_diff = a.iter().symmetric_difference_by_key(b.iter(), |&&(id, _)| id);
+        sym_diff.next();
+
+        let (lo, hi) = sym_diff.size_hint();
+        let count = sym_diff.count();
+        assert!(lo <= count);
+        assert!(hi.map_or(true, |h| h >= count));
+    }
+
     #[bench]
     #[cfg(all(feature = "nightly", test))]
     pub fn inner_join_map(b: &mut self::test::Bencher) {